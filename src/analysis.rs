@@ -1,22 +1,71 @@
 use std::convert::Into;
+use std::f64::consts::PI;
 use crate::complex::Complex;
-use crate::fft::fft;
+use crate::fft::{fft, rfft, FFTVec};
 
 struct FrequencyComponent {
     f: f64,
     coeff: f64,
 }
 
-fn get_primary_frequencies<T>(mut samples: Vec<T>, sample_rate: u32, threshold: f64) -> Vec<FrequencyComponent> where T: Into<f64> {
+/// A window function applied to a block of samples before an FFT, to
+/// reduce the spectral leakage that comes from tones not landing exactly
+/// on a bin center.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Window {
+    Rectangular,
+    Hann,
+    Hamming,
+    BlackmanHarris,
+}
+
+impl Window {
+    /// The coefficient for sample `n` of an `n_samples`-long window.
+    fn coefficient(&self, n: usize, n_samples: usize) -> f64 {
+        let n = n as f64;
+        let len = (n_samples - 1) as f64;
+        match self {
+            Window::Rectangular => 1.0,
+            Window::Hann => 0.5 * (1.0 - (2.0 * PI * n / len).cos()),
+            Window::Hamming => 0.54 - 0.46 * (2.0 * PI * n / len).cos(),
+            Window::BlackmanHarris =>
+                0.35875
+                    - 0.48829 * (2.0 * PI * n / len).cos()
+                    + 0.14128 * (4.0 * PI * n / len).cos()
+                    - 0.01168 * (6.0 * PI * n / len).cos(),
+        }
+    }
+
+    /// The coherent gain of the window over `n_samples`: the mean of its
+    /// coefficients. Windowing attenuates amplitude, so magnitudes should
+    /// be divided by this to stay comparable across windows.
+    pub fn coherent_gain(&self, n_samples: usize) -> f64 {
+        (0 .. n_samples).map(|n| self.coefficient(n, n_samples)).sum::<f64>() / n_samples as f64
+    }
+}
+
+/// Multiplies each sample in `vec` by its window coefficient, in place.
+pub fn apply_window(vec: &mut FFTVec, window: Window) {
+    let n = vec.data.len();
+    for (i, sample) in vec.data.iter_mut().enumerate() {
+        let coeff = window.coefficient(i, n);
+        *sample = Complex::new(sample.re * coeff, sample.im * coeff);
+    }
+}
+
+fn get_primary_frequencies<T>(mut samples: Vec<T>, sample_rate: u32, threshold: f64, window: Window) -> Vec<FrequencyComponent> where T: Into<f64> {
     let n = samples.len();
     let bin_size = (sample_rate as f64) / (n as f64);
     let f64_samples: Vec<f64> = samples.drain(..).map(|s| s.into()).collect();
-    let freq_domain: Vec<f64> = fft(f64_samples).data.drain(..)
-        .map(|s| s.mag() / (n as f64))
-        .take(n/2)
+    let mut windowed: FFTVec = f64_samples.into();
+    apply_window(&mut windowed, window);
+    let gain = window.coherent_gain(n);
+    let real_samples: Vec<f64> = windowed.data.iter().map(|c| c.re).collect();
+    let freq_domain: Vec<f64> = rfft(real_samples).data.iter()
+        .map(|s| s.mag() / (n as f64 * gain))
         .collect();
-    freq_domain.windows(3).enumerate().filter_map(|(k_prev, window)| {
-        let (prev, curr, next) = (window[0], window[1], window[2]);
+    freq_domain.windows(3).enumerate().filter_map(|(k_prev, triplet)| {
+        let (prev, curr, next) = (triplet[0], triplet[1], triplet[2]);
         if curr > threshold && curr > prev && curr > next {
             Some(FrequencyComponent {
                 f: (k_prev + 1) as f64 * bin_size,
@@ -28,6 +77,56 @@ fn get_primary_frequencies<T>(mut samples: Vec<T>, sample_rate: u32, threshold:
     }).collect()
 }
 
+struct PsdBin {
+    f: f64,
+    power: f64,
+}
+
+/// Estimates the power spectral density of `samples` using Welch's method:
+/// the signal is split into overlapping, windowed segments of length
+/// `segment_len`, each segment's periodogram (`|FFT|^2`) is computed, and
+/// the periodograms are averaged together. Averaging many short,
+/// overlapping segments trades frequency resolution for much lower
+/// variance than a single FFT of the whole signal, which is what makes
+/// this the standard approach for noisy real-world audio.
+fn power_spectral_density<T>(mut samples: Vec<T>, sample_rate: u32, segment_len: usize, overlap: f64, window: Window) -> Vec<PsdBin> where T: Into<f64> {
+    let f64_samples: Vec<f64> = samples.drain(..).map(|s| s.into()).collect();
+    assert!(overlap < 1.0, "overlap must be less than 1.0, got {}", overlap);
+    assert!(segment_len <= f64_samples.len(), "segment_len ({}) must not exceed the number of samples ({})", segment_len, f64_samples.len());
+    let step = ((segment_len as f64) * (1.0 - overlap)) as usize;
+    assert!(step > 0, "segment_len ({}) and overlap ({}) leave no step between segments", segment_len, overlap);
+    let window_power: f64 = (0 .. segment_len).map(|n| window.coefficient(n, segment_len).powi(2)).sum();
+    let n_bins = segment_len / 2;
+    let bin_size = (sample_rate as f64) / (segment_len as f64);
+
+    let mut accum = vec![0.0; n_bins];
+    let mut n_segments = 0;
+    let mut start = 0;
+    while start + segment_len <= f64_samples.len() {
+        let segment = &f64_samples[start .. start + segment_len];
+        let mean = segment.iter().sum::<f64>() / segment_len as f64;
+        let centered: Vec<f64> = segment.iter().map(|s| s - mean).collect();
+        let mut windowed: FFTVec = centered.into();
+        apply_window(&mut windowed, window);
+        let spectrum = fft(windowed);
+        for (bin, c) in spectrum.data.iter().take(n_bins).enumerate() {
+            // One-sided PSD: every bin except DC represents both the positive
+            // and the discarded negative-frequency half of the spectrum, so
+            // its power must be doubled to keep the total power correct.
+            let one_sided_factor = if bin == 0 { 1.0 } else { 2.0 };
+            accum[bin] += one_sided_factor * c.mag().powi(2);
+        }
+        n_segments += 1;
+        start += step;
+    }
+
+    let scale = 1.0 / (sample_rate as f64 * window_power * n_segments as f64);
+    (0 .. n_bins).map(|k| PsdBin {
+        f: k as f64 * bin_size,
+        power: accum[k] * scale,
+    }).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -39,8 +138,45 @@ mod tests {
         let mut reader = hound::WavReader::open("sine.wav").unwrap();
         let samples: Vec<i16> = reader.samples::<i16>().flat_map(|s| s).take(8192).collect();
         let sample_rate = reader.spec().sample_rate;
-        let freqs = get_primary_frequencies(samples, sample_rate, 20.0);
+        let freqs = get_primary_frequencies(samples, sample_rate, 20.0, Window::Hann);
         assert!(freqs.len() == 1);
         assert!(freqs[0].f - 440.0 < 10.0);
     }
+
+    #[test]
+    fn window_endpoints_taper_to_zero() {
+        // Hamming's constant term is tuned to minimize sidelobes rather
+        // than to vanish at the edges, so only Hann and Blackman-Harris
+        // are checked here.
+        let n = 8;
+        for window in [Window::Hann, Window::BlackmanHarris] {
+            let first = window.coefficient(0, n);
+            let last = window.coefficient(n - 1, n);
+            assert!(first.abs() < 1e-3, "{:?} should start near zero, got {}", window, first);
+            assert!(last.abs() < 1e-3, "{:?} should end near zero, got {}", window, last);
+        }
+    }
+
+    #[test]
+    fn rectangular_window_is_a_no_op() {
+        let n = 8;
+        assert_eq!(Window::Rectangular.coherent_gain(n), 1.0);
+
+        let mut vec: FFTVec = vec![1.0; n].into();
+        let before = FFTVec { data: vec.data.clone() };
+        apply_window(&mut vec, Window::Rectangular);
+        assert_eq!(vec, before);
+    }
+
+    #[test]
+    fn psd_finds_peak_near_tone_frequency() {
+        let sample_rate = 8000;
+        let freq = 440.0;
+        let samples: Vec<f64> = (0..8000).map(|i| {
+            (2.0 * PI * freq * i as f64 / sample_rate as f64).sin()
+        }).collect();
+        let psd = power_spectral_density(samples, sample_rate, 256, 0.5, Window::Hann);
+        let peak = psd.iter().max_by(|a, b| a.power.partial_cmp(&b.power).unwrap()).unwrap();
+        assert!((peak.f - freq).abs() < 20.0, "peak at {} Hz, expected near {}", peak.f, freq);
+    }
 }
@@ -0,0 +1,158 @@
+use crate::complex::Complex;
+use crate::fft::{fft, ifft};
+
+/// Computes the full linear convolution of `a` and `b` via the FFT: both
+/// inputs are zero-padded to a common power-of-two length, multiplied
+/// bin-by-bin in the frequency domain, and transformed back, turning an
+/// `O(p*q)` direct convolution into `O(n*log n)`.
+pub fn convolve(a: &[f64], b: &[f64]) -> Vec<f64> {
+    let out_len = a.len() + b.len() - 1;
+    let n = out_len.next_power_of_two();
+
+    let mut padded_a = vec![0.0; n];
+    padded_a[..a.len()].copy_from_slice(a);
+    let mut padded_b = vec![0.0; n];
+    padded_b[..b.len()].copy_from_slice(b);
+
+    let spectrum_a = fft(padded_a);
+    let spectrum_b = fft(padded_b);
+    let product: Vec<Complex> = spectrum_a.data.iter().zip(spectrum_b.data.iter())
+        .map(|(x, y)| *x * *y)
+        .collect();
+
+    ifft(product).data[..out_len].iter().map(|c| c.re).collect()
+}
+
+/// Multiplies two polynomials represented by their coefficient vectors
+/// `a` and `b` (lowest-degree term first) via the FFT. This is the exact
+/// same computation as [`convolve`], exposed under the name most useful
+/// to callers doing polynomial or big-integer-style multiplication,
+/// turning schoolbook `O(p*q)` multiplication into `O(n*log n)`.
+pub fn poly_mul(a: &[f64], b: &[f64]) -> Vec<f64> {
+    convolve(a, b)
+}
+
+/// Recovers the input to a linear system from its `output` and the
+/// system's `impulse_response`, the frequency-domain inverse of
+/// convolution: both are transformed to a common power-of-two length,
+/// their spectra are divided bin-by-bin, and the quotient is transformed
+/// back.
+pub fn deconvolve(output: &[f64], impulse_response: &[f64]) -> Vec<f64> {
+    let n = output.len().max(impulse_response.len()).next_power_of_two();
+
+    let mut padded_output = vec![0.0; n];
+    padded_output[..output.len()].copy_from_slice(output);
+    let mut padded_response = vec![0.0; n];
+    padded_response[..impulse_response.len()].copy_from_slice(impulse_response);
+
+    let spectrum_output = fft(padded_output);
+    let spectrum_response = fft(padded_response);
+    let quotient: Vec<Complex> = spectrum_output.data.iter().zip(spectrum_response.data.iter())
+        .map(|(y, h)| *y / *h)
+        .collect();
+
+    ifft(quotient).data[..output.len()].iter().map(|c| c.re).collect()
+}
+
+/// Applies the FIR filter `kernel` to `signal` via overlap-save: the
+/// kernel's spectrum is precomputed once, and the signal is processed in
+/// power-of-two blocks much larger than the kernel, each block carrying
+/// the previous `kernel.len() - 1` samples as context so that only the
+/// non-aliased tail of each block's circular convolution is kept. This
+/// is what lets low-pass/high-pass/band-pass filtering of a long signal
+/// run in `O(n*log N)` instead of `O(n*M)`.
+pub fn fir_filter(signal: &[f64], kernel: &[f64]) -> Vec<f64> {
+    let m = kernel.len();
+    let block_len = (8 * m).next_power_of_two().max(64);
+    let valid_len = block_len - m + 1;
+
+    let mut padded_kernel = vec![0.0; block_len];
+    padded_kernel[..m].copy_from_slice(kernel);
+    let h = fft(padded_kernel);
+
+    let mut context = vec![0.0; m - 1];
+    let mut output = Vec::with_capacity(signal.len());
+    let mut start = 0;
+    while start < signal.len() {
+        let end = (start + valid_len).min(signal.len());
+
+        let mut non_padded = context.clone();
+        non_padded.extend_from_slice(&signal[start..end]);
+        let mut block = non_padded.clone();
+        block.resize(block_len, 0.0);
+
+        let spectrum = fft(block);
+        let product: Vec<Complex> = spectrum.data.iter().zip(h.data.iter())
+            .map(|(x, y)| *x * *y)
+            .collect();
+        let filtered = ifft(product);
+
+        let produced = end - start;
+        output.extend(filtered.data[m - 1 .. m - 1 + produced].iter().map(|c| c.re));
+
+        let keep_from = non_padded.len() - (m - 1);
+        context = non_padded[keep_from..].to_vec();
+        start = end;
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn direct_convolve(a: &[f64], b: &[f64]) -> Vec<f64> {
+        let mut out = vec![0.0; a.len() + b.len() - 1];
+        for (i, &a_i) in a.iter().enumerate() {
+            for (j, &b_j) in b.iter().enumerate() {
+                out[i + j] += a_i * b_j;
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn convolve_matches_direct_convolution() {
+        let a = vec![1.0, 2.0, 3.0];
+        let b = vec![0.0, 1.0, 0.5];
+        let result = convolve(&a, &b);
+        let expected = direct_convolve(&a, &b);
+        for (actual, expected) in result.iter().zip(expected.iter()) {
+            assert!((actual - expected).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn poly_mul_matches_direct_convolution() {
+        let a = vec![1.0, -2.0, 0.5, 3.0, -1.0];
+        let b = vec![2.0, 0.0, -1.5];
+        let result = poly_mul(&a, &b);
+        let expected = direct_convolve(&a, &b);
+        for (actual, expected) in result.iter().zip(expected.iter()) {
+            assert!((actual - expected).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn deconvolve_recovers_input_from_convolved_output() {
+        let input = vec![1.0, -2.0, 3.0, 0.5, -1.5];
+        let impulse_response = vec![1.0, 0.5, 0.25];
+        let output = direct_convolve(&input, &impulse_response);
+        let recovered = deconvolve(&output, &impulse_response);
+        for (actual, expected) in recovered.iter().zip(input.iter()) {
+            assert!((actual - expected).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn fir_filter_matches_direct_convolution_across_blocks() {
+        let kernel = vec![0.25, 0.25, 0.25, 0.25];
+        let signal: Vec<f64> = (0..500).map(|i| (i as f64 * 0.3).sin()).collect();
+        let result = fir_filter(&signal, &kernel);
+        let expected = direct_convolve(&signal, &kernel);
+        assert_eq!(result.len(), signal.len());
+        for (actual, expected) in result.iter().zip(expected.iter().take(signal.len())) {
+            assert!((actual - expected).abs() < 1e-6);
+        }
+    }
+}
@@ -24,26 +24,86 @@ impl FFTVec {
 
 impl From<Vec<Complex>> for FFTVec {
     fn from(v: Vec<Complex>) -> FFTVec {
-        FFTVec { data: pad_if_necessary(v) }
+        FFTVec { data: v }
     }
 }
 
-fn pad_if_necessary(mut v: Vec<Complex>) -> Vec<Complex> {
-    let len = v.len();
-    let is_power_of_2 = (len & (len - 1)) == 0;
-    if !is_power_of_2 {
-        let next_pow_of_2 = (len as f64).log2().floor() as i32 + 1;
-        let padded_size = (2.0 as f64).powi(next_pow_of_2) as usize;
-        let padding = vec![ZERO; padded_size - len];
-        v.extend(padding);
-    }
-    v
-}
-
 impl From<Vec<f64>> for FFTVec {
     fn from(mut v: Vec<f64>) -> FFTVec {
         let complex = v.drain(..).map(|x| Complex::new(x, 0.0)).collect();
-        FFTVec { data: pad_if_necessary(complex) }
+        FFTVec { data: complex }
+    }
+}
+
+fn is_pow2(n: usize) -> bool {
+    n != 0 && (n & (n - 1)) == 0
+}
+
+fn reverse_bits(mut x: usize, bits: u32) -> usize {
+    let mut result = 0;
+    for _ in 0 .. bits {
+        result = (result << 1) | (x & 1);
+        x >>= 1;
+    }
+    result
+}
+
+/// A reusable iterative Cooley-Tukey plan for a fixed power-of-two size `N`.
+/// Building a plan precomputes the bit-reversal permutation and the twiddle
+/// table `W[k] = exp(-2*pi*i*k/N)` for `k in 0..N/2`, so repeated transforms
+/// of the same size (e.g. per-segment in Welch's method or per-block in
+/// overlap-save filtering) don't re-derive them or reallocate on every call.
+#[derive(Debug, PartialEq)]
+pub struct FftPlan {
+    n: usize,
+    bit_reversal: Vec<usize>,
+    twiddles: Vec<Complex>,
+}
+
+impl FftPlan {
+    /// Builds a plan for transforms of length `n`, which must be a power of two.
+    pub fn new(n: usize) -> FftPlan {
+        assert!(is_pow2(n), "FftPlan requires a power-of-two size, got {}", n);
+        let bits = n.trailing_zeros();
+        let bit_reversal = (0 .. n).map(|i| reverse_bits(i, bits)).collect();
+        let twiddles = (0 .. n/2)
+            .map(|k| exp_i(-2.0 * PI * k as f64 / n as f64))
+            .collect();
+        FftPlan { n, bit_reversal, twiddles }
+    }
+
+    /// Transforms `data` in place (forward, or inverse-unnormalized when
+    /// `inverse` is set). `data.len()` must equal the plan's `n`.
+    ///
+    /// First applies the bit-reversal permutation, then runs `log2(n)`
+    /// stages of butterflies over strides `2, 4, 8, ...`, reading twiddles
+    /// from the precomputed table (conjugated for the inverse).
+    pub fn process(&self, data: &mut [Complex], inverse: bool) {
+        assert_eq!(data.len(), self.n, "data length must match the plan's size");
+
+        for i in 0 .. self.n {
+            let j = self.bit_reversal[i];
+            if j > i {
+                data.swap(i, j);
+            }
+        }
+
+        let mut stride = 2;
+        while stride <= self.n {
+            let half = stride / 2;
+            let twiddle_stride = self.n / stride;
+            for start in (0 .. self.n).step_by(stride) {
+                for k in 0 .. half {
+                    let w = self.twiddles[k * twiddle_stride];
+                    let w = if inverse { Complex::new(w.re, -w.im) } else { w };
+                    let x_k = data[start + k];
+                    let t = w * data[start + k + half];
+                    data[start + k] = x_k + t;
+                    data[start + k + half] = x_k - t;
+                }
+            }
+            stride *= 2;
+        }
     }
 }
 
@@ -61,26 +121,109 @@ pub fn ifft<T>(vec: T) -> FFTVec where T: Into<FFTVec> {
     result
 }
 
+/// Computes the DFT of `n` real samples, returning only the `n/2 + 1`
+/// non-redundant bins: a real signal's spectrum is conjugate-symmetric
+/// (`X_k = conj(X_{N-k})`), so the upper half is redundant and not worth
+/// computing or storing.
+///
+/// For even `n = 2m` this is done with a single `m`-point complex FFT: the
+/// samples are packed two-per-complex (even samples as the real part, odd
+/// as the imaginary part, giving an `m`-length complex signal `z`),
+/// transformed once, then unscrambled with the even/odd split formula
+/// `X_k = (Z_k + conj(Z_{m-k}))/2 - i*exp(-pi*i*k/m)*(Z_k - conj(Z_{m-k}))/2`.
+/// Odd `n` can't be packed this way, so it falls back to a plain FFT and
+/// keeps only the unique half of the spectrum.
+pub fn rfft(samples: Vec<f64>) -> FFTVec {
+    let n = samples.len();
+    assert!(n > 0, "rfft requires a non-empty input");
+
+    if !n.is_multiple_of(2) {
+        let mut data = fft_general(samples.into(), false).data;
+        data.truncate(n / 2 + 1);
+        return FFTVec { data };
+    }
+    let m = n / 2;
+
+    let packed: Vec<Complex> = (0 .. m)
+        .map(|i| Complex::new(samples[2*i], samples[2*i + 1]))
+        .collect();
+    let z = fft_general(FFTVec { data: packed }, false).data;
+
+    let data = (0 ..= m).map(|k| {
+        let z_k = z[k % m];
+        let z_mk = z[(m - k) % m];
+        let z_mk_conj = Complex::new(z_mk.re, -z_mk.im);
+        let even = Complex::new((z_k.re + z_mk_conj.re) / 2.0, (z_k.im + z_mk_conj.im) / 2.0);
+        let odd = Complex::new((z_k.re - z_mk_conj.re) / 2.0, (z_k.im - z_mk_conj.im) / 2.0);
+        let w = exp_i(-PI * k as f64 / m as f64);
+        let neg_i_w = Complex::new(w.im, -w.re);
+        even + neg_i_w * odd
+    }).collect();
+
+    FFTVec { data }
+}
+
+/// Computes `X_k = sum_n x_n * exp(-2*pi*i*n*k/N)` (or its unnormalized
+/// inverse when `inverse` is set) for any length `N`. Powers of two go
+/// through the fast radix-2 Cooley-Tukey path; every other length goes
+/// through Bluestein's chirp-z transform, which reduces an arbitrary-N DFT
+/// to a power-of-2 convolution instead of silently zero-padding the input.
 fn fft_general(vec: FFTVec, inverse: bool) -> FFTVec {
-    let n = vec.data.len();
-    if n == 1 {
-        vec
+    if is_pow2(vec.data.len()) {
+        fft_pow2(vec, inverse)
     } else {
-        let (even, odd) = vec.split();
-        let mut result = fft_general(even, inverse);
-        result.data.extend(fft_general(odd, inverse).data);
-        for k in 0 .. n/2 {
-            let x_k = result.data[k];
-            let w = if inverse {
-                exp_i(2.0 * PI * (k as f64 / n as f64))
-            } else {
-                exp_i(-2.0 * PI * (k as f64 / n as f64))
-            };
-            result.data[k] = x_k + w * result.data[k + n/2];
-            result.data[k + n/2] = x_k - w * result.data[k + n/2];
+        bluestein(vec, inverse)
+    }
+}
+
+fn fft_pow2(vec: FFTVec, inverse: bool) -> FFTVec {
+    let mut data = vec.data;
+    FftPlan::new(data.len()).process(&mut data, inverse);
+    FFTVec { data }
+}
+
+/// Bluestein's algorithm: rewrites the arbitrary-N DFT as a linear
+/// convolution using `n*k = (n^2 + k^2 - (k-n)^2) / 2`, so
+/// `X_k = exp(-pi*i*k^2/N) * sum_n (x_n * exp(-pi*i*n^2/N)) * exp(pi*i*(k-n)^2/N)`.
+/// The inner sum is the convolution of the chirped input `a` with the chirp
+/// `b`, computed via the power-of-2 FFT path. `inverse` flips the sign of
+/// every exponent, matching `fft_pow2`'s unnormalized-inverse convention.
+fn bluestein(vec: FFTVec, inverse: bool) -> FFTVec {
+    let n = vec.data.len();
+    let sign = if inverse { 1.0 } else { -1.0 };
+    let m = (2 * n - 1).next_power_of_two();
+
+    let chirp: Vec<Complex> = (0 .. n)
+        .map(|i| exp_i(sign * PI * (i * i) as f64 / n as f64))
+        .collect();
+
+    let mut a = vec![ZERO; m];
+    for (i, x_i) in vec.data.iter().enumerate() {
+        a[i] = *x_i * chirp[i];
+    }
+
+    let mut b = vec![ZERO; m];
+    for i in 0 .. n {
+        let b_i = exp_i(-sign * PI * (i * i) as f64 / n as f64);
+        b[i] = b_i;
+        if i != 0 {
+            b[m - i] = b_i;
         }
-        result
     }
+
+    let a_fft = fft_pow2(FFTVec { data: a }, false);
+    let b_fft = fft_pow2(FFTVec { data: b }, false);
+    let product: Vec<Complex> = a_fft.data.iter().zip(b_fft.data.iter())
+        .map(|(x, y)| *x * *y)
+        .collect();
+
+    let mut conv = fft_pow2(FFTVec { data: product }, true);
+    for c in conv.data.iter_mut() {
+        *c = Complex::new(c.re / m as f64, c.im / m as f64);
+    }
+
+    let data = (0 .. n).map(|k| chirp[k] * conv.data[k]).collect();
+    FFTVec { data }
 }
 
 #[cfg(test)]
@@ -88,15 +231,15 @@ mod tests {
     use super::*;
 
     #[test]
-    fn padding() {
+    fn no_padding_for_arbitrary_length() {
         let no_padding: FFTVec = vec![ZERO; 4].into();
         assert_eq!(no_padding, FFTVec {
             data: vec![ZERO; 4]
         });
 
-        let needs_padding: FFTVec = vec![ZERO; 5].into();
-        assert_eq!(needs_padding, FFTVec {
-            data: vec![ZERO; 8]
+        let still_unpadded: FFTVec = vec![ZERO; 5].into();
+        assert_eq!(still_unpadded, FFTVec {
+            data: vec![ZERO; 5]
         });
     }
 
@@ -150,6 +293,108 @@ mod tests {
         ]});
     }
 
+    #[test]
+    fn fft_plan_is_reusable_across_buffers_and_directions() {
+        let plan = FftPlan::new(4);
+
+        let mut a = vec![
+            Complex::new(1.0, 0.0),
+            Complex::new(2.0, -1.0),
+            Complex::new(0.0, -1.0),
+            Complex::new(-1.0, 2.0),
+        ];
+        plan.process(&mut a, false);
+        assert_eq!(a, vec![
+            Complex::new(2.0, 0.0),
+            Complex::new(-2.0, -2.0),
+            Complex::new(0.0, -2.0),
+            Complex::new(4.0, 4.0),
+        ]);
+
+        let mut b = vec![
+            Complex::new(0.0, 0.0),
+            Complex::new(1.0, 0.0),
+            Complex::new(0.0, 0.0),
+            Complex::new(-1.0, 0.0),
+        ];
+        plan.process(&mut b, false);
+        let expected_b = [
+            Complex::new(0.0, 0.0),
+            Complex::new(0.0, -2.0),
+            Complex::new(0.0, 0.0),
+            Complex::new(0.0, 2.0),
+        ];
+        for (actual, expected) in b.iter().zip(expected_b.iter()) {
+            assert!((actual.re - expected.re).abs() < 1e-9);
+            assert!((actual.im - expected.im).abs() < 1e-9);
+        }
+
+        let mut roundtrip = a.clone();
+        plan.process(&mut roundtrip, true);
+        for c in roundtrip.iter_mut() {
+            *c = Complex::new(c.re / 4.0, c.im / 4.0);
+        }
+        assert_eq!(roundtrip, vec![
+            Complex::new(1.0, 0.0),
+            Complex::new(2.0, -1.0),
+            Complex::new(0.0, -1.0),
+            Complex::new(-1.0, 2.0),
+        ]);
+    }
+
+    #[test]
+    fn bluestein_arbitrary_length() {
+        let input = vec![
+            Complex::new(1.0, 0.0),
+            Complex::new(2.0, -1.0),
+            Complex::new(0.0, -1.0),
+            Complex::new(-1.0, 2.0),
+            Complex::new(3.0, 0.0),
+        ];
+        let expected = [
+            Complex::new(5.0000000000, 0.0000000000),
+            Complex::new(0.6396896931, -0.7547627247),
+            Complex::new(-1.0887176697, 2.6568757573),
+            Complex::new(-5.6194862628, -0.4208077798),
+            Complex::new(6.0685142394, -1.4813052528),
+        ];
+
+        let result = fft(input.clone());
+        for (actual, expected) in result.data.iter().zip(expected.iter()) {
+            assert!((actual.re - expected.re).abs() < 1e-6);
+            assert!((actual.im - expected.im).abs() < 1e-6);
+        }
+
+        let roundtrip = ifft(result.data);
+        for (actual, original) in roundtrip.data.iter().zip(input.iter()) {
+            assert!((actual.re - original.re).abs() < 1e-6);
+            assert!((actual.im - original.im).abs() < 1e-6);
+        }
+    }
+
+    fn brute_force_dft(samples: &[f64]) -> Vec<Complex> {
+        let n = samples.len();
+        (0 .. n).map(|k| {
+            samples.iter().enumerate().fold(ZERO, |acc, (i, &x_i)| {
+                acc + Complex::new(x_i, 0.0) * exp_i(-2.0 * PI * (i * k) as f64 / n as f64)
+            })
+        }).collect()
+    }
+
+    #[test]
+    fn rfft_matches_brute_force_dft_for_even_and_odd_lengths() {
+        for n in [4, 5, 8, 9] {
+            let samples: Vec<f64> = (0 .. n).map(|i| (i as f64 * 0.37).sin()).collect();
+            let expected = brute_force_dft(&samples);
+            let result = rfft(samples);
+            assert_eq!(result.data.len(), n / 2 + 1);
+            for (actual, expected) in result.data.iter().zip(expected.iter()) {
+                assert!((actual.re - expected.re).abs() < 1e-9);
+                assert!((actual.im - expected.im).abs() < 1e-9);
+            }
+        }
+    }
+
     #[test]
     fn test_wav() {
         use hound;